@@ -30,7 +30,7 @@ use smallvec::SmallVec;
 #[cfg(not(feature = "smallvec"))]
 use std::collections::VecDeque;
 
-use std::iter::{DoubleEndedIterator, ExactSizeIterator};
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
 
 
 /// Adds a peekable_nth() method to types that implement [`std::iter::Iterator`].
@@ -51,6 +51,8 @@ where
     next: SmallVec<[I::Item; 64]>,
     #[cfg(not(feature = "smallvec"))]
     next: VecDeque<I::Item>,
+    peek_index: usize,
+    exhausted: bool,
 }
 
 impl<I> IteratorExt for I
@@ -65,6 +67,8 @@ where
             next: SmallVec::new(),
             #[cfg(not(feature = "smallvec"))]
             next: VecDeque::new(),
+            peek_index: 0,
+            exhausted: false,
         }
     }
 }
@@ -82,14 +86,82 @@ where
     /// Returns a reference to the nth(n) value without advancing the iterator.
     #[inline]
     pub fn peek_nth(&mut self, n: usize) -> Option<&I::Item> {
-        for _ in self.next.len()..=n {
-            #[cfg(feature = "smallvec")]
-            self.next.push(self.iter.next()?);
-            #[cfg(not(feature = "smallvec"))]
-            self.next.push_back(self.iter.next()?);
+        self.fill(n);
+        self.next.get(n)
+    }
+
+    /// Fills the buffer with elements pulled from `iter` until it holds at least `n + 1`
+    /// elements or `iter` is exhausted.
+    fn fill(&mut self, n: usize) {
+        while !self.exhausted && self.next.len() <= n {
+            match self.iter.next() {
+                #[cfg(feature = "smallvec")]
+                Some(item) => self.next.push(item),
+                #[cfg(not(feature = "smallvec"))]
+                Some(item) => self.next.push_back(item),
+                None => self.exhausted = true,
+            }
         }
+    }
 
-        self.next.get(n)
+    /// Returns a mutable reference to the next value without advancing the iterator.
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut I::Item> {
+        self.peek_nth_mut(0)
+    }
+
+    /// Returns a mutable reference to the nth(n) value without advancing the iterator.
+    #[inline]
+    pub fn peek_nth_mut(&mut self, n: usize) -> Option<&mut I::Item> {
+        self.fill(n);
+        self.next.get_mut(n)
+    }
+
+    /// Consumes and returns the next value if `func` returns `true` when called on it.
+    #[inline]
+    pub fn next_if(&mut self, func: impl FnOnce(&I::Item) -> bool) -> Option<I::Item> {
+        match self.peek() {
+            Some(item) if func(item) => self.next(),
+            _ => None,
+        }
+    }
+
+    /// Consumes and returns the next value if it is equal to `expected`.
+    #[inline]
+    pub fn next_if_eq<T>(&mut self, expected: &T) -> Option<I::Item>
+    where
+        I::Item: PartialEq<T>,
+    {
+        self.next_if(|item| item == expected)
+    }
+
+    /// Returns an iterator that yields and consumes elements from the front only while `pred`
+    /// returns `true`, leaving the first non-matching element buffered.
+    #[inline]
+    pub fn peeking_take_while<P>(&mut self, pred: P) -> PeekingTakeWhile<'_, I, P>
+    where
+        P: FnMut(&I::Item) -> bool,
+    {
+        PeekingTakeWhile { iter: self, pred }
+    }
+
+    /// Returns a reference to the item at an internal peek cursor and then advances the cursor
+    /// by one element, without consuming anything. The cursor does not advance past the end.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&I::Item> {
+        let index = self.peek_index;
+        let has_item = self.peek_nth(index).is_some();
+        if has_item {
+            self.peek_index += 1;
+        }
+        self.next.get(index)
+    }
+
+    /// Rewinds the [`peek_next`](PeekableNth::peek_next) cursor back to the front of the
+    /// iterator.
+    #[inline]
+    pub fn reset_peek(&mut self) {
+        self.peek_index = 0;
     }
 }
 
@@ -99,11 +171,24 @@ where
 {
     #[inline]
     fn next_back(&mut self) -> Option<I::Item> {
+        self.peek_index = 0;
+
+        if self.exhausted {
+            #[cfg(feature = "smallvec")]
+            return self.next.pop();
+            #[cfg(not(feature = "smallvec"))]
+            return self.next.pop_back();
+        }
+
         match self.iter.next_back() {
             #[cfg(feature = "smallvec")]
             None if !self.next.is_empty() => self.next.pop(),
             #[cfg(not(feature = "smallvec"))]
             None if !self.next.is_empty() => self.next.pop_back(),
+            None => {
+                self.exhausted = true;
+                None
+            }
             option => option,
         }
     }
@@ -115,7 +200,7 @@ where
 {
     #[inline]
     fn len(&self) -> usize {
-        self.iter.len()
+        self.iter.len() + self.next.len()
     }
 }
 
@@ -127,8 +212,17 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<I::Item> {
+        self.peek_index = 0;
         if self.next.is_empty() {
-            self.iter.next()
+            if self.exhausted {
+                return None;
+            }
+
+            let item = self.iter.next();
+            if item.is_none() {
+                self.exhausted = true;
+            }
+            item
         } else {
             #[cfg(feature = "smallvec")]
             return Some(self.next.remove(0));
@@ -136,4 +230,327 @@ where
             return self.next.pop_front();
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.next.len();
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|upper| upper.checked_add(buffered)),
+        )
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        let buffered = self.next.len();
+        if self.exhausted {
+            return buffered;
+        }
+
+        buffered + self.iter.count()
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<I::Item> {
+        while !self.next.is_empty() {
+            let item = self.next();
+            if n == 0 {
+                return item;
+            }
+            n -= 1;
+        }
+
+        if self.exhausted {
+            return None;
+        }
+
+        let item = self.iter.nth(n);
+        if item.is_none() {
+            self.exhausted = true;
+        }
+        item
+    }
+
+    fn last(mut self) -> Option<I::Item> {
+        if self.exhausted {
+            #[cfg(feature = "smallvec")]
+            return self.next.pop();
+            #[cfg(not(feature = "smallvec"))]
+            return self.next.pop_back();
+        }
+
+        match self.iter.last() {
+            Some(item) => Some(item),
+            #[cfg(feature = "smallvec")]
+            None => self.next.pop(),
+            #[cfg(not(feature = "smallvec"))]
+            None => self.next.pop_back(),
+        }
+    }
+}
+
+impl<I> FusedIterator for PeekableNth<I> where I: FusedIterator {}
+
+/// An iterator that yields and consumes elements from the front of a [`PeekableNth`] only while
+/// a predicate holds, created by [`PeekableNth::peeking_take_while`].
+pub struct PeekingTakeWhile<'a, I, P>
+where
+    I: Iterator,
+{
+    iter: &'a mut PeekableNth<I>,
+    pred: P,
+}
+
+impl<'a, I, P> Iterator for PeekingTakeWhile<'a, I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next_if(&mut self.pred)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IteratorExt;
+
+    /// An iterator that yields `None` once after its `values` are exhausted and then resumes
+    /// yielding from `resume`, used to prove that `PeekableNth` never polls a non-fused iterator
+    /// again once it has reported exhaustion.
+    struct Resuming {
+        values: std::vec::IntoIter<i32>,
+        resume: std::vec::IntoIter<i32>,
+        yielded_none: bool,
+    }
+
+    impl Iterator for Resuming {
+        type Item = i32;
+
+        fn next(&mut self) -> Option<i32> {
+            if let Some(item) = self.values.next() {
+                return Some(item);
+            }
+
+            if !self.yielded_none {
+                self.yielded_none = true;
+                return None;
+            }
+
+            self.resume.next()
+        }
+    }
+
+    #[test]
+    fn nth_sets_exhausted_flag_so_iter_is_not_polled_again() {
+        let iter = Resuming {
+            values: vec![1, 2, 3].into_iter(),
+            resume: vec![4, 5].into_iter(),
+            yielded_none: false,
+        };
+        let mut p = iter.peekable_nth();
+
+        assert_eq!(p.nth(5), None);
+        assert_eq!(p.peek_nth(0), None);
+        assert_eq!(p.next(), None);
+    }
+
+    #[test]
+    fn count_does_not_poll_iter_once_exhausted() {
+        let iter = Resuming {
+            values: vec![1, 2, 3].into_iter(),
+            resume: vec![4, 5].into_iter(),
+            yielded_none: false,
+        };
+        let mut p = iter.peekable_nth();
+
+        assert_eq!(p.peek_nth(3), None);
+
+        assert_eq!(p.count(), 3);
+    }
+
+    #[test]
+    fn last_does_not_poll_iter_once_exhausted() {
+        let iter = Resuming {
+            values: vec![1, 2, 3].into_iter(),
+            resume: vec![4, 5].into_iter(),
+            yielded_none: false,
+        };
+        let mut p = iter.peekable_nth();
+
+        assert_eq!(p.peek_nth(3), None);
+
+        assert_eq!(p.last(), Some(3));
+    }
+
+    /// A `DoubleEndedIterator` whose `next_back` pulls from an `extra` source that is only
+    /// reachable from the back, used to prove that `next_back` does not poll `iter` once the
+    /// front has reported exhaustion.
+    struct FrontExhausts {
+        main: std::collections::VecDeque<i32>,
+        extra: Vec<i32>,
+    }
+
+    impl Iterator for FrontExhausts {
+        type Item = i32;
+
+        fn next(&mut self) -> Option<i32> {
+            self.main.pop_front()
+        }
+    }
+
+    impl DoubleEndedIterator for FrontExhausts {
+        fn next_back(&mut self) -> Option<i32> {
+            self.extra.pop()
+        }
+    }
+
+    #[test]
+    fn next_back_does_not_poll_iter_once_exhausted() {
+        let mut p = FrontExhausts {
+            main: vec![1, 2, 3].into(),
+            extra: vec![4, 5],
+        }
+        .peekable_nth();
+
+        assert_eq!(p.next(), Some(1));
+        assert_eq!(p.next(), Some(2));
+        assert_eq!(p.next(), Some(3));
+        assert_eq!(p.next(), None);
+
+        assert_eq!(p.next_back(), None);
+    }
+
+    #[test]
+    fn peek_mut_edits_the_buffered_front_element() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        if let Some(item) = p.peek_mut() {
+            *item = 10;
+        }
+
+        assert_eq!(p.collect::<Vec<_>>(), vec![10, 2, 3]);
+    }
+
+    #[test]
+    fn peek_nth_mut_edits_the_nth_buffered_element() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        if let Some(item) = p.peek_nth_mut(1) {
+            *item = 20;
+        }
+
+        assert_eq!(p.collect::<Vec<_>>(), vec![1, 20, 3]);
+    }
+
+    #[test]
+    fn next_if_only_consumes_on_a_match() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        assert_eq!(p.next_if(|&item| item == 2), None);
+        assert_eq!(p.next_if(|&item| item == 1), Some(1));
+        assert_eq!(p.collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn next_if_eq_only_consumes_on_a_match() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        assert_eq!(p.next_if_eq(&2), None);
+        assert_eq!(p.next_if_eq(&1), Some(1));
+        assert_eq!(p.collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn size_hint_includes_buffered_elements() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        p.peek_nth(1);
+
+        assert_eq!(p.size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn len_includes_buffered_elements() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        p.peek_nth(1);
+
+        assert_eq!(p.len(), 3);
+    }
+
+    #[test]
+    fn count_includes_buffered_elements() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        p.peek_nth(0);
+
+        assert_eq!(p.count(), 3);
+    }
+
+    #[test]
+    fn nth_drains_the_buffer_before_the_inner_iterator() {
+        let mut p = vec![1, 2, 3, 4].into_iter().peekable_nth();
+
+        p.peek_nth(1);
+
+        assert_eq!(p.nth(2), Some(3));
+        assert_eq!(p.next(), Some(4));
+    }
+
+    #[test]
+    fn last_consults_the_buffer_when_iter_is_exhausted() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        p.peek_nth(2);
+
+        assert_eq!(p.last(), Some(3));
+    }
+
+    #[test]
+    fn peek_next_walks_forward_without_consuming() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        assert_eq!(p.peek_next(), Some(&1));
+        assert_eq!(p.peek_next(), Some(&2));
+        assert_eq!(p.peek_next(), Some(&3));
+        assert_eq!(p.peek_next(), None);
+
+        assert_eq!(p.collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn reset_peek_rewinds_the_cursor_to_the_front() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        p.peek_next();
+        p.peek_next();
+        p.reset_peek();
+
+        assert_eq!(p.peek_next(), Some(&1));
+    }
+
+    #[test]
+    fn next_resets_the_peek_cursor() {
+        let mut p = vec![1, 2, 3].into_iter().peekable_nth();
+
+        p.peek_next();
+        p.peek_next();
+        p.next();
+
+        assert_eq!(p.peek_next(), Some(&2));
+    }
+
+    #[test]
+    fn peeking_take_while_leaves_the_boundary_element_buffered() {
+        let mut p = vec![1, 2, 3, 10, 4].into_iter().peekable_nth();
+
+        let run: Vec<_> = p.peeking_take_while(|&item| item < 10).collect();
+
+        assert_eq!(run, vec![1, 2, 3]);
+        assert_eq!(p.collect::<Vec<_>>(), vec![10, 4]);
+    }
 }